@@ -0,0 +1,351 @@
+use cosmwasm_std::{
+    entry_point, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdResult, WasmMsg,
+};
+use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cw_rate_limiter::Rate;
+
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionList, ConfigResponse, ExecuteMsg, InstantiateMsg, PendingQueueResponse,
+    ProxyExecuteMsg, QueryMsg,
+};
+use crate::state::{
+    Config, ALLOWLIST, COLLECTION_RATES, COLLECTION_STATE, CONFIG, DENYLIST, PENDING_QUEUE,
+    RATE_LIMITER,
+};
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let origin = msg
+        .origin
+        .map(|o| deps.api.addr_validate(&o))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
+    let admin = msg
+        .admin
+        .map(|a| deps.api.addr_validate(&a))
+        .transpose()?
+        .unwrap_or(info.sender);
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            origin: origin.clone(),
+            admin: admin.clone(),
+            buffer: msg.buffer.unwrap_or(false),
+        },
+    )?;
+    RATE_LIMITER.init(deps.storage, msg.rate_limit, &env.block)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("origin", origin)
+        .add_attribute("admin", admin))
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig {
+            rate_limit,
+            origin,
+            admin,
+            buffer,
+        } => execute_update_config(deps, env, info, rate_limit, origin, admin, buffer),
+        ExecuteMsg::SetCollectionRate { collection, rate } => {
+            execute_set_collection_rate(deps, info, collection, rate)
+        }
+        ExecuteMsg::RemoveCollectionRate { collection } => {
+            execute_remove_collection_rate(deps, info, collection)
+        }
+        ExecuteMsg::AddToList { list, collection } => {
+            execute_update_list(deps, info, list, collection, true)
+        }
+        ExecuteMsg::RemoveFromList { list, collection } => {
+            execute_update_list(deps, info, list, collection, false)
+        }
+        ExecuteMsg::Drain { max } => execute_drain(deps, env, max),
+    }
+}
+
+fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let collection = info.sender.clone();
+
+    if DENYLIST.has(deps.storage, &collection) {
+        return Err(ContractError::CollectionDenied {
+            collection: collection.into_string(),
+        });
+    }
+
+    if !ALLOWLIST.has(deps.storage, &collection) {
+        if let Err(err) = check_collection_rate(deps.storage, &collection, &env) {
+            if matches!(err, ContractError::RateLimit(_)) && CONFIG.load(deps.storage)?.buffer {
+                let mut queue = PENDING_QUEUE
+                    .may_load(deps.storage, &collection)?
+                    .unwrap_or_default();
+                queue.push(msg);
+                PENDING_QUEUE.save(deps.storage, &collection, &queue)?;
+
+                return Ok(Response::new()
+                    .add_attribute("method", "receive_nft")
+                    .add_attribute("buffered", "true"));
+            }
+            return Err(err);
+        }
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(forward_to_origin(&config.origin, &collection, msg)?)
+        .add_attribute("method", "receive_nft"))
+}
+
+fn forward_to_origin(
+    origin: &Addr,
+    eyeball: &Addr,
+    msg: Cw721ReceiveMsg,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: origin.to_string(),
+        msg: to_binary(&ProxyExecuteMsg::ReceiveProxyNft {
+            eyeball: eyeball.to_string(),
+            msg,
+        })?,
+        funds: vec![],
+    })
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rate_limit: Option<Rate>,
+    origin: Option<String>,
+    admin: Option<String>,
+    buffer: Option<bool>,
+) -> Result<Response, ContractError> {
+    let mut config = require_admin(deps.as_ref(), &info)?;
+
+    if let Some(rate_limit) = rate_limit {
+        RATE_LIMITER.set_rate(deps.storage, rate_limit, &env.block)?;
+    }
+    if let Some(origin) = origin {
+        config.origin = deps.api.addr_validate(&origin)?;
+    }
+    if let Some(admin) = admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(buffer) = buffer {
+        config.buffer = buffer;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "update_config"))
+}
+
+fn execute_set_collection_rate(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    rate: Rate,
+) -> Result<Response, ContractError> {
+    require_admin(deps.as_ref(), &info)?;
+
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTION_RATES.save(deps.storage, &collection, &rate)?;
+    // Reset tracking so a switch between a block-based and time-based
+    // rate doesn't compare a stale height against a timestamp.
+    COLLECTION_STATE.remove(deps.storage, &collection);
+
+    Ok(Response::new()
+        .add_attribute("method", "set_collection_rate")
+        .add_attribute("collection", collection))
+}
+
+fn execute_remove_collection_rate(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+) -> Result<Response, ContractError> {
+    require_admin(deps.as_ref(), &info)?;
+
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTION_RATES.remove(deps.storage, &collection);
+    COLLECTION_STATE.remove(deps.storage, &collection);
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_collection_rate")
+        .add_attribute("collection", collection))
+}
+
+fn execute_update_list(
+    deps: DepsMut,
+    info: MessageInfo,
+    list: CollectionList,
+    collection: String,
+    add: bool,
+) -> Result<Response, ContractError> {
+    require_admin(deps.as_ref(), &info)?;
+
+    let collection = deps.api.addr_validate(&collection)?;
+    let list_map = match list {
+        CollectionList::Allow => ALLOWLIST,
+        CollectionList::Deny => DENYLIST,
+    };
+    if add {
+        list_map.save(deps.storage, &collection, &Empty {})?;
+    } else {
+        list_map.remove(deps.storage, &collection);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_list")
+        .add_attribute("collection", collection))
+}
+
+/// Forwards up to `max` queued NFTs whose collection's rate budget has
+/// reopened. Permissionless: anyone may help drain the buffer once
+/// the chain has caught up, same as anyone may pay to unstick a queue.
+fn execute_drain(deps: DepsMut, env: Env, max: Option<u32>) -> Result<Response, ContractError> {
+    let max = max.unwrap_or(u32::MAX);
+    let config = CONFIG.load(deps.storage)?;
+
+    let collections = PENDING_QUEUE
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut drained = 0u32;
+    for collection in collections {
+        // A collection denylisted after its NFTs were queued stays held:
+        // draining is permissionless, and forwarding it onward here would
+        // let anyone undo the denylist for NFTs already in escrow.
+        if DENYLIST.has(deps.storage, &collection) {
+            continue;
+        }
+
+        let mut queue = PENDING_QUEUE.load(deps.storage, &collection)?;
+
+        while drained < max && !queue.is_empty() {
+            if check_collection_rate(deps.storage, &collection, &env).is_err() {
+                break;
+            }
+            let msg = queue.remove(0);
+            // `origin` must actually hold the NFT before it's told
+            // about it, same as a normal `SendNft` transfers ownership
+            // before invoking the receive hook.
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: collection.to_string(),
+                    msg: to_binary(&Cw721ExecuteMsg::<Empty>::TransferNft {
+                        recipient: config.origin.to_string(),
+                        token_id: msg.token_id.clone(),
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+            messages.push(forward_to_origin(&config.origin, &collection, msg)?.into());
+            drained += 1;
+        }
+
+        if queue.is_empty() {
+            PENDING_QUEUE.remove(deps.storage, &collection);
+        } else {
+            PENDING_QUEUE.save(deps.storage, &collection, &queue)?;
+        }
+
+        if drained >= max {
+            break;
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("method", "drain")
+        .add_attribute("drained", drained.to_string()))
+}
+
+fn require_admin(deps: Deps, info: &MessageInfo) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(config)
+}
+
+/// Checks `collection`'s rate limit, using its override in
+/// `COLLECTION_RATES` if one is set, or the default rate otherwise.
+fn check_collection_rate(
+    storage: &mut dyn cosmwasm_std::Storage,
+    collection: &Addr,
+    env: &Env,
+) -> Result<(), ContractError> {
+    let rate = match COLLECTION_RATES.may_load(storage, collection)? {
+        Some(rate) => rate,
+        None => return Ok(RATE_LIMITER.check(storage, &env.block)?),
+    };
+
+    let mut state = COLLECTION_STATE
+        .may_load(storage, collection)?
+        .unwrap_or_default();
+    rate.check(&mut state, &env.block)?;
+    COLLECTION_STATE.save(storage, collection, &state)?;
+
+    Ok(())
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::PendingQueue {} => to_binary(&query_pending_queue(deps)?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let rate_limit = RATE_LIMITER
+        .rate(deps.storage)
+        .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+    Ok(ConfigResponse {
+        rate_limit,
+        origin: config.origin.into_string(),
+        admin: config.admin.into_string(),
+        buffer: config.buffer,
+    })
+}
+
+fn query_pending_queue(deps: Deps) -> StdResult<PendingQueueResponse> {
+    let tokens = PENDING_QUEUE
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            entry.map(|(collection, queue)| {
+                queue
+                    .into_iter()
+                    .map(move |msg| (collection.to_string(), msg.token_id))
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    Ok(PendingQueueResponse { tokens })
+}
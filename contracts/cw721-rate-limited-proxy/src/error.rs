@@ -0,0 +1,18 @@
+use cosmwasm_std::StdError;
+use cw_rate_limiter::RateLimitError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    RateLimit(#[from] RateLimitError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("collection {collection} is denylisted")]
+    CollectionDenied { collection: String },
+}
@@ -0,0 +1,95 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw721::Cw721ReceiveMsg;
+use cw_rate_limiter::Rate;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The rate limit applied to incoming NFTs.
+    pub rate_limit: Rate,
+    /// The address that rate-limited NFTs are forwarded to. Defaults to
+    /// the sender of the instantiate message if not set.
+    pub origin: Option<String>,
+    /// The address allowed to call `ExecuteMsg::UpdateConfig`. Defaults
+    /// to the sender of the instantiate message if not set.
+    pub admin: Option<String>,
+    /// If true, NFTs that arrive over the rate limit are escrowed and
+    /// queued for later delivery via `ExecuteMsg::Drain` instead of
+    /// causing the transfer to fail. Defaults to `false`.
+    pub buffer: Option<bool>,
+}
+
+/// Which per-collection set an `AddToList` or `RemoveFromList` message
+/// targets.
+#[cw_serde]
+pub enum CollectionList {
+    Allow,
+    Deny,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Updates the rate limit, origin, and/or admin. Only callable by
+    /// the current admin. Fields left as `None` are unchanged.
+    UpdateConfig {
+        rate_limit: Option<Rate>,
+        origin: Option<String>,
+        admin: Option<String>,
+        buffer: Option<bool>,
+    },
+    /// Sets a rate limit override for a single collection, independent
+    /// of the default rate. Admin-only.
+    SetCollectionRate { collection: String, rate: Rate },
+    /// Removes a collection's rate override, falling back to the
+    /// default rate. Admin-only.
+    RemoveCollectionRate { collection: String },
+    /// Adds a collection to the allow or deny list. Admin-only.
+    AddToList {
+        list: CollectionList,
+        collection: String,
+    },
+    /// Removes a collection from the allow or deny list. Admin-only.
+    RemoveFromList {
+        list: CollectionList,
+        collection: String,
+    },
+    /// Forwards up to `max` queued NFTs whose collection's rate budget
+    /// has reopened, transferring each onward to `origin`. Callable by
+    /// anyone; a no-op if nothing is eligible to drain yet.
+    Drain { max: Option<u32> },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(PendingQueueResponse)]
+    PendingQueue {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub rate_limit: Rate,
+    pub origin: String,
+    pub admin: String,
+    pub buffer: bool,
+}
+
+#[cw_serde]
+pub struct PendingQueueResponse {
+    /// `(collection, token_id)` pairs. Token ids are only unique within a
+    /// single collection, so each entry is tagged with the collection it
+    /// is queued against.
+    pub tokens: Vec<(String, String)>,
+}
+
+/// The message forwarded on to `origin` once an incoming NFT clears the
+/// rate limit.
+#[cw_serde]
+pub enum ProxyExecuteMsg {
+    ReceiveProxyNft {
+        eyeball: String,
+        msg: Cw721ReceiveMsg,
+    },
+}
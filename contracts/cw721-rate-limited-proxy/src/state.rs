@@ -0,0 +1,37 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Empty};
+use cw721::Cw721ReceiveMsg;
+use cw_rate_limiter::{RateLimitState, Ratelimiter};
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    /// The address that rate-limited NFTs are forwarded to.
+    pub origin: Addr,
+    /// The address allowed to update the config.
+    pub admin: Addr,
+    /// If true, over-limit NFTs are queued instead of rejected. See
+    /// `PENDING_QUEUE`.
+    pub buffer: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The default rate applied to collections with no override in
+/// `COLLECTION_RATES`.
+pub const RATE_LIMITER: Ratelimiter = Ratelimiter::new("rate", "rate__state");
+
+/// Per-collection rate overrides, keyed by the cw721's address.
+pub const COLLECTION_RATES: Map<&Addr, cw_rate_limiter::Rate> = Map::new("collection_rates");
+/// Rate-limit tracking state for collections in `COLLECTION_RATES`.
+pub const COLLECTION_STATE: Map<&Addr, RateLimitState> = Map::new("collection_rate_state");
+
+/// Collections that bypass rate limiting entirely.
+pub const ALLOWLIST: Map<&Addr, Empty> = Map::new("allowlist");
+/// Collections that are rejected outright, regardless of rate.
+pub const DENYLIST: Map<&Addr, Empty> = Map::new("denylist");
+
+/// NFTs that arrived while a collection was over its rate limit and
+/// `Config::buffer` was set, awaiting `ExecuteMsg::Drain`. FIFO per
+/// collection.
+pub const PENDING_QUEUE: Map<&Addr, Vec<Cw721ReceiveMsg>> = Map::new("pending_queue");
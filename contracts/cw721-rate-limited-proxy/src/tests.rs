@@ -3,7 +3,7 @@ use cw721_base::MintMsg;
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
 use cw_rate_limiter::Rate;
 
-use crate::msg::InstantiateMsg;
+use crate::msg::{CollectionList, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
 
 struct Test {
     pub app: App,
@@ -38,7 +38,7 @@ impl Test {
             .instantiate_contract(
                 rate_limiter_id,
                 minter.clone(),
-                &InstantiateMsg::new(rate, Some(mock_receiver.to_string())),
+                &InstantiateMsg::new(rate, Some(mock_receiver.to_string()), None, None),
                 &[],
                 "rate_limiter",
                 None,
@@ -130,6 +130,7 @@ impl Test {
         use rand::seq::SliceRandom;
 
         let start_block = self.app.block_info().height;
+        let start_time = self.app.block_info().time;
         for _ in 0..for_blocks {
             match rate {
                 Rate::PerBlock(n) => {
@@ -144,17 +145,125 @@ impl Test {
                         self.send_nft_and_check_received(nft)?;
                     }
                 }
+                Rate::PerSeconds { window_secs, .. } => {
+                    let elapsed = self.app.block_info().time.seconds() - start_time.seconds();
+                    if elapsed % window_secs == 0 {
+                        let nft = self.cw721s.choose(rng).unwrap().clone();
+                        self.send_nft_and_check_received(nft)?;
+                    }
+                }
             }
-            self.app.update_block(next_block)
+            self.app.update_block(|b| {
+                b.height += 1;
+                b.time = b.time.plus_seconds(1);
+            })
         }
 
         Ok(())
     }
+
+    pub fn query_config(&self) -> ConfigResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.rate_limiter, &QueryMsg::Config {})
+            .unwrap()
+    }
+
+    pub fn update_config(
+        &mut self,
+        sender: Addr,
+        rate_limit: Option<Rate>,
+        origin: Option<String>,
+        admin: Option<String>,
+        buffer: Option<bool>,
+    ) -> Result<(), anyhow::Error> {
+        self.app
+            .execute_contract(
+                sender,
+                self.rate_limiter.clone(),
+                &ExecuteMsg::UpdateConfig {
+                    rate_limit,
+                    origin,
+                    admin,
+                    buffer,
+                },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn drain(&mut self, max: Option<u32>) -> Result<(), anyhow::Error> {
+        self.app
+            .execute_contract(
+                self.minter.clone(),
+                self.rate_limiter.clone(),
+                &ExecuteMsg::Drain { max },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn query_pending_queue(&self) -> Vec<(String, String)> {
+        self.app
+            .wrap()
+            .query_wasm_smart::<crate::msg::PendingQueueResponse>(
+                &self.rate_limiter,
+                &QueryMsg::PendingQueue {},
+            )
+            .unwrap()
+            .tokens
+    }
+
+    pub fn set_collection_rate(&mut self, collection: Addr, rate: Rate) -> Result<(), anyhow::Error> {
+        self.app
+            .execute_contract(
+                self.minter.clone(),
+                self.rate_limiter.clone(),
+                &ExecuteMsg::SetCollectionRate {
+                    collection: collection.to_string(),
+                    rate,
+                },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn update_list(
+        &mut self,
+        list: CollectionList,
+        collection: Addr,
+        add: bool,
+    ) -> Result<(), anyhow::Error> {
+        let msg = if add {
+            ExecuteMsg::AddToList {
+                list,
+                collection: collection.to_string(),
+            }
+        } else {
+            ExecuteMsg::RemoveFromList {
+                list,
+                collection: collection.to_string(),
+            }
+        };
+        self.app
+            .execute_contract(self.minter.clone(), self.rate_limiter.clone(), &msg, &[])
+            .map(|_| ())
+    }
 }
 
 impl InstantiateMsg {
-    fn new(rate_limit: Rate, origin: Option<String>) -> Self {
-        Self { rate_limit, origin }
+    fn new(
+        rate_limit: Rate,
+        origin: Option<String>,
+        admin: Option<String>,
+        buffer: Option<bool>,
+    ) -> Self {
+        Self {
+            rate_limit,
+            origin,
+            admin,
+            buffer,
+        }
     }
 }
 
@@ -214,6 +323,284 @@ fn test_simple() {
     test.send_nfts_at_rate(rng, actual, 1).unwrap();
 }
 
+#[test]
+fn update_config() {
+    let mut test = Test::new(1, Rate::Blocks(1));
+    let admin = test.minter.clone();
+
+    let config = test.query_config();
+    assert_eq!(config.rate_limit, Rate::Blocks(1));
+    assert_eq!(config.admin, admin);
+
+    test.update_config(admin, Some(Rate::PerBlock(7)), None, None, None)
+        .unwrap();
+
+    let config = test.query_config();
+    assert_eq!(config.rate_limit, Rate::PerBlock(7));
+}
+
+#[test]
+fn update_config_requires_admin() {
+    let mut test = Test::new(1, Rate::Blocks(1));
+    let err = test
+        .update_config(
+            Addr::unchecked("not-the-admin"),
+            Some(Rate::PerBlock(7)),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<crate::error::ContractError>().unwrap(),
+        crate::error::ContractError::Unauthorized {}
+    );
+}
+
+#[test]
+fn per_seconds_rate_limits_by_wall_clock_time() {
+    let mut test = Test::new(
+        1,
+        Rate::PerSeconds {
+            limit: 2,
+            window_secs: 10,
+        },
+    );
+    let nft = test.cw721s[0].clone();
+
+    test.send_nft_and_check_received(nft.clone()).unwrap();
+    test.send_nft_and_check_received(nft.clone()).unwrap();
+    test.send_nft_and_check_received(nft.clone()).unwrap_err();
+
+    // Window hasn't elapsed yet.
+    test.app.update_block(|b| b.time = b.time.plus_seconds(5));
+    test.send_nft_and_check_received(nft.clone()).unwrap_err();
+
+    // Window has now rolled over.
+    test.app.update_block(|b| b.time = b.time.plus_seconds(5));
+    test.send_nft_and_check_received(nft).unwrap();
+}
+
+#[test]
+fn send_nfts_at_rate_honors_per_seconds_rate() {
+    let rng = &mut rand::thread_rng();
+    let limit = Rate::PerSeconds {
+        limit: 1,
+        window_secs: 3,
+    };
+    let mut test = Test::new(5, limit);
+    test.send_nfts_at_rate(rng, limit, 10).unwrap();
+}
+
+#[test]
+fn exhausting_one_collection_does_not_block_another() {
+    let mut test = Test::new(2, Rate::PerBlock(1));
+    let tight = test.cw721s[0].clone();
+    let loose = test.cw721s[1].clone();
+
+    test.set_collection_rate(tight.clone(), Rate::PerBlock(1))
+        .unwrap();
+    test.set_collection_rate(loose.clone(), Rate::PerBlock(3))
+        .unwrap();
+
+    test.send_nft_and_check_received(tight.clone()).unwrap();
+    // `tight`'s budget for this block is spent, but `loose` is tracked
+    // independently and should still go through.
+    test.send_nft_and_check_received(tight).unwrap_err();
+    test.send_nft_and_check_received(loose.clone()).unwrap();
+    test.send_nft_and_check_received(loose).unwrap();
+}
+
+#[test]
+fn denylisted_collection_is_rejected() {
+    let mut test = Test::new(1, Rate::PerBlock(100));
+    let nft = test.cw721s[0].clone();
+
+    test.update_list(CollectionList::Deny, nft.clone(), true)
+        .unwrap();
+
+    test.send_nft_and_check_received(nft).unwrap_err();
+}
+
+#[test]
+fn allowlisted_collection_bypasses_rate_limit() {
+    let mut test = Test::new(1, Rate::PerBlock(1));
+    let nft = test.cw721s[0].clone();
+
+    test.update_list(CollectionList::Allow, nft.clone(), true)
+        .unwrap();
+
+    for _ in 0..5 {
+        test.send_nft_and_check_received(nft.clone()).unwrap();
+    }
+}
+
+#[test]
+fn over_limit_nft_is_buffered_instead_of_rejected() {
+    let mut test = Test::new(1, Rate::PerBlock(1));
+    let admin = test.minter.clone();
+    test.update_config(admin, None, None, None, Some(true))
+        .unwrap();
+
+    let nft = test.cw721s[0].clone();
+
+    // First send spends the budget; the next one is expected to be
+    // buffered rather than rejected.
+    test.send_nft_and_check_received(nft.clone()).unwrap();
+
+    test.nfts_minted += 1;
+    let token_id = test.nfts_minted.to_string();
+
+    test.app
+        .execute_contract(
+            test.minter.clone(),
+            nft.clone(),
+            &cw721_base::msg::ExecuteMsg::<Empty, Empty>::Mint(MintMsg::<Empty> {
+                token_id: token_id.clone(),
+                owner: test.minter.to_string(),
+                token_uri: None,
+                extension: Default::default(),
+            }),
+            &[],
+        )
+        .unwrap();
+    test.app
+        .execute_contract(
+            test.minter.clone(),
+            nft.clone(),
+            &cw721_base::msg::ExecuteMsg::<Empty, Empty>::SendNft {
+                contract: test.rate_limiter.to_string(),
+                token_id: token_id.clone(),
+                msg: to_binary("hello").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        test.query_pending_queue(),
+        vec![(nft.to_string(), token_id)]
+    );
+}
+
+#[test]
+fn drain_forwards_queued_nfts_once_budget_reopens() {
+    let mut test = Test::new(1, Rate::PerBlock(1));
+    let admin = test.minter.clone();
+    test.update_config(admin, None, None, None, Some(true))
+        .unwrap();
+    let nft = test.cw721s[0].clone();
+
+    // First send clears the rate limit; the second is buffered.
+    test.send_nft_and_check_received(nft.clone()).unwrap();
+    test.nfts_minted += 1;
+    let queued_token_id = test.nfts_minted.to_string();
+    test.app
+        .execute_contract(
+            test.minter.clone(),
+            nft.clone(),
+            &cw721_base::msg::ExecuteMsg::<Empty, Empty>::Mint(MintMsg::<Empty> {
+                token_id: queued_token_id.clone(),
+                owner: test.minter.to_string(),
+                token_uri: None,
+                extension: Default::default(),
+            }),
+            &[],
+        )
+        .unwrap();
+    test.app
+        .execute_contract(
+            test.minter.clone(),
+            nft.clone(),
+            &cw721_base::msg::ExecuteMsg::<Empty, Empty>::SendNft {
+                contract: test.rate_limiter.to_string(),
+                token_id: queued_token_id.clone(),
+                msg: to_binary("hello").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        test.query_pending_queue(),
+        vec![(nft.to_string(), queued_token_id.clone())]
+    );
+
+    // The budget hasn't reopened yet; draining is a no-op.
+    test.drain(None).unwrap();
+    assert_eq!(
+        test.query_pending_queue(),
+        vec![(nft.to_string(), queued_token_id)]
+    );
+
+    test.app.update_block(next_block);
+    test.drain(None).unwrap();
+    assert!(test.query_pending_queue().is_empty());
+
+    let owner: cw721::OwnerOfResponse = test
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &nft,
+            &cw721_base::msg::QueryMsg::<Empty>::OwnerOf {
+                token_id: queued_token_id,
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, test.mock_receiver);
+}
+
+#[test]
+fn drain_holds_queue_for_denylisted_collection() {
+    let mut test = Test::new(1, Rate::PerBlock(1));
+    let admin = test.minter.clone();
+    test.update_config(admin, None, None, None, Some(true))
+        .unwrap();
+    let nft = test.cw721s[0].clone();
+
+    // First send clears the rate limit; the second is buffered.
+    test.send_nft_and_check_received(nft.clone()).unwrap();
+    test.nfts_minted += 1;
+    let queued_token_id = test.nfts_minted.to_string();
+    test.app
+        .execute_contract(
+            test.minter.clone(),
+            nft.clone(),
+            &cw721_base::msg::ExecuteMsg::<Empty, Empty>::Mint(MintMsg::<Empty> {
+                token_id: queued_token_id.clone(),
+                owner: test.minter.to_string(),
+                token_uri: None,
+                extension: Default::default(),
+            }),
+            &[],
+        )
+        .unwrap();
+    test.app
+        .execute_contract(
+            test.minter.clone(),
+            nft.clone(),
+            &cw721_base::msg::ExecuteMsg::<Empty, Empty>::SendNft {
+                contract: test.rate_limiter.to_string(),
+                token_id: queued_token_id.clone(),
+                msg: to_binary("hello").unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Denylisting after the NFT is already queued must hold it, not let
+    // a later permissionless `Drain` forward it anyway.
+    test.update_list(CollectionList::Deny, nft.clone(), true)
+        .unwrap();
+
+    test.app.update_block(next_block);
+    test.drain(None).unwrap();
+    assert_eq!(
+        test.query_pending_queue(),
+        vec![(nft.to_string(), queued_token_id)]
+    );
+}
+
 #[test]
 fn fuzz_rate_limiting() {
     let iterations = 500;
@@ -245,4 +632,66 @@ fn fuzz_rate_limiting() {
             test.app.update_block(next_block)
         }
     }
+}
+
+#[test]
+fn fuzz_per_collection_rate_limiting() {
+    use rand::Rng;
+
+    let iterations = 500;
+    let max = 5;
+    let range = 1..max;
+    let rng = &mut rand::thread_rng();
+
+    let mut test = Test::new(max as usize, Rate::PerBlock(1));
+
+    // Give every collection its own independent `PerBlock` rate, distinct
+    // from the instantiate-time default, so this only exercises
+    // per-collection tracking. `Rate::Blocks` is deliberately excluded:
+    // it only opens back up after N blocks pass since its last send, so
+    // an "other" collection picked on a later iteration could still be
+    // inside its own cooldown window and make the cross-collection
+    // assertion below flaky.
+    let limits: Vec<Rate> = test
+        .cw721s
+        .clone()
+        .into_iter()
+        .map(|nft| {
+            let rate = Rate::PerBlock(rng.gen_range(range.clone()));
+            test.set_collection_rate(nft, rate).unwrap();
+            rate
+        })
+        .collect();
+
+    for _ in 0..iterations {
+        let exhausted_idx = rng.gen_range(0..test.cw721s.len());
+        let exhausted = test.cw721s[exhausted_idx].clone();
+        let Rate::PerBlock(exhausted_limit) = limits[exhausted_idx] else {
+            unreachable!("collections are only ever assigned Rate::PerBlock above")
+        };
+        let over_limit = exhausted_limit + 1;
+
+        let mut budget_exceeded = false;
+        for _ in 0..over_limit {
+            if test
+                .send_nft_and_check_received(exhausted.clone())
+                .is_err()
+            {
+                budget_exceeded = true;
+            }
+        }
+        assert!(
+            budget_exceeded,
+            "expected collection {}'s budget ({:?}) to be exceeded",
+            exhausted, limits[exhausted_idx]
+        );
+
+        // A different collection's budget must be unaffected by the
+        // one above being exhausted.
+        let other_idx = (exhausted_idx + 1) % test.cw721s.len();
+        let other = test.cw721s[other_idx].clone();
+        test.send_nft_and_check_received(other).unwrap();
+
+        test.app.update_block(next_block);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,11 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RateLimitError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("rate limit exceeded")]
+    RateLimitExceeded {},
+}
@@ -0,0 +1,33 @@
+use cosmwasm_std::{
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+};
+use cw_storage_plus::Item;
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+/// The most recently received execute message, so that tests can assert
+/// on what the proxy under test forwarded to us.
+const LAST_MSG: Item<ExecuteMsg> = Item::new("last_msg");
+
+#[entry_point]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[entry_point]
+pub fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    LAST_MSG.save(deps.storage, &msg)?;
+    Ok(Response::default())
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::LastMsg {} => to_binary(&LAST_MSG.load(deps.storage)?),
+    }
+}
@@ -0,0 +1,19 @@
+use cosmwasm_schema::cw_serde;
+use cw721::Cw721ReceiveMsg;
+
+#[cw_serde]
+#[derive(Default)]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    ReceiveProxyNft {
+        eyeball: String,
+        msg: Cw721ReceiveMsg,
+    },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    LastMsg {},
+}